@@ -0,0 +1,92 @@
+use std::fmt;
+use std::path::PathBuf;
+
+/// Severity of a single parse diagnostic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Error => write!(f, "error"),
+            Severity::Warning => write!(f, "warning"),
+        }
+    }
+}
+
+/// A single parse error or warning, carrying the byte span into the
+/// original source that it applies to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub span: std::ops::Range<usize>,
+    pub severity: Severity,
+    pub message: String,
+    /// The file this diagnostic applies to, for diagnostics raised while
+    /// resolving `include`d files. `None` means the diagnostic applies to
+    /// whatever source text the caller parsed directly.
+    pub file: Option<PathBuf>,
+}
+
+impl Diagnostic {
+    pub fn new(span: std::ops::Range<usize>, severity: Severity, message: impl Into<String>) -> Self {
+        Self {
+            span,
+            severity,
+            message: message.into(),
+            file: None,
+        }
+    }
+
+    pub fn with_file(mut self, file: impl Into<PathBuf>) -> Self {
+        self.file = Some(file.into());
+        self
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(file) = &self.file {
+            write!(f, "{}: ", file.display())?;
+        }
+        write!(
+            f,
+            "{}: {} ({}..{})",
+            self.severity, self.message, self.span.start, self.span.end
+        )
+    }
+}
+
+/// Render a set of diagnostics as caret-underlined reports against `source`,
+/// in the style `ariadne` produces for `openqasm-rs`.
+pub fn render_diagnostics(source: &str, diagnostics: &[Diagnostic]) -> String {
+    use ariadne::{Color, Label, Report, ReportKind, Source};
+
+    let mut out = Vec::new();
+    for diagnostic in diagnostics {
+        let kind = match diagnostic.severity {
+            Severity::Error => ReportKind::Error,
+            Severity::Warning => ReportKind::Warning,
+        };
+        let color = match diagnostic.severity {
+            Severity::Error => Color::Red,
+            Severity::Warning => Color::Yellow,
+        };
+
+        let mut buf = Vec::new();
+        Report::build(kind, (), diagnostic.span.start)
+            .with_message(&diagnostic.message)
+            .with_label(
+                Label::new(diagnostic.span.clone())
+                    .with_message(&diagnostic.message)
+                    .with_color(color),
+            )
+            .finish()
+            .write(Source::from(source), &mut buf)
+            .expect("writing a diagnostic report should not fail");
+        out.push(String::from_utf8_lossy(&buf).into_owned());
+    }
+    out.join("\n")
+}
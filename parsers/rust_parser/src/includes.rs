@@ -0,0 +1,374 @@
+use crate::diagnostics::{Diagnostic, Severity};
+use crate::tokens::tokenize_qasm3;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+/// How `include "...";` statements are handled when parsing from a file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IncludePolicy {
+    /// Resolve each include against the search path, recursively.
+    Resolve,
+    /// Leave `include` statements exactly as written, without touching the
+    /// filesystem. Whatever they declare will typically come back as
+    /// unresolved from the parser.
+    Ignore,
+    /// Refuse to touch the filesystem for includes at all and report every
+    /// `include` statement as an error. Use this to sandbox untrusted input.
+    Deny,
+}
+
+/// Directories searched for `include` targets, in order.
+///
+/// Populated from an explicit list plus the `QASM_PATH` environment
+/// variable, mirroring how the Qiskit OQ3 parser locates includes and how
+/// C compilers honor `-I` flags together with `CPATH`.
+#[derive(Debug, Clone, Default)]
+pub struct SearchPath(Vec<PathBuf>);
+
+impl SearchPath {
+    pub fn new(dirs: impl IntoIterator<Item = PathBuf>) -> Self {
+        Self(dirs.into_iter().collect())
+    }
+
+    /// Build a search path from `QASM_PATH` alone.
+    pub fn from_env() -> Self {
+        let dirs = std::env::var_os("QASM_PATH")
+            .map(|value| std::env::split_paths(&value).collect())
+            .unwrap_or_default();
+        Self(dirs)
+    }
+
+    /// Add `dir` to the front of the search path so it is tried first.
+    pub fn prepend(&mut self, dir: PathBuf) {
+        self.0.insert(0, dir);
+    }
+
+    /// Resolve `name` against each search directory in turn, refusing any
+    /// target that could escape the directory it's found in (an absolute
+    /// path, a `..` component, or a symlink that resolves outside of it).
+    fn find(&self, name: &str) -> Option<PathBuf> {
+        let target = Path::new(name);
+        if target.is_absolute()
+            || target
+                .components()
+                .any(|component| component == Component::ParentDir)
+        {
+            return None;
+        }
+
+        self.0.iter().find_map(|dir| {
+            let candidate = dir.join(target);
+            if !candidate.is_file() {
+                return None;
+            }
+            let canonical_dir = fs::canonicalize(dir).ok()?;
+            let canonical_candidate = fs::canonicalize(&candidate).ok()?;
+            canonical_candidate
+                .starts_with(&canonical_dir)
+                .then_some(candidate)
+        })
+    }
+}
+
+/// A contiguous run of the composed source that came verbatim from one
+/// original file, used to map a diagnostic's span in the composed text back
+/// to the real file and offset it came from.
+pub(crate) struct Segment {
+    /// Range within the composed text this segment covers.
+    composed: std::ops::Range<usize>,
+    file: PathBuf,
+    /// Offset within `file` that `composed.start` corresponds to.
+    file_offset: usize,
+}
+
+/// Read `path` and recursively inline every `include`d file reachable from
+/// it, honoring `policy` and searching `search_path` for include targets.
+///
+/// Include cycles are detected by tracking the canonical paths on the
+/// current inclusion chain; a file that re-includes an ancestor is reported
+/// as a cycle rather than recursed into. Returns the composed source
+/// alongside a source map that [`attribute_diagnostics`] uses to point
+/// diagnostics raised against the composed text back at their real file and
+/// offset.
+pub(crate) fn inline_includes(
+    path: &Path,
+    policy: IncludePolicy,
+    search_path: &SearchPath,
+) -> Result<(String, Vec<Segment>), Vec<Diagnostic>> {
+    let mut visited = HashSet::new();
+    inline_includes_rec(path, policy, search_path, &mut visited)
+}
+
+/// Remap diagnostics raised against the composed source produced by
+/// [`inline_includes`] so their span and `file` refer to the original file
+/// the offending text came from.
+///
+/// A diagnostic whose span crosses a segment boundary (e.g. an unclosed
+/// brace in an included file whose "body" the parser resolves into the
+/// including file) is split into one diagnostic per segment it overlaps,
+/// each clipped to that segment's bounds, rather than attributing the whole
+/// span — file included — to whichever segment merely contains its start.
+pub(crate) fn attribute_diagnostics(
+    diagnostics: Vec<Diagnostic>,
+    segments: &[Segment],
+) -> Vec<Diagnostic> {
+    diagnostics
+        .into_iter()
+        .flat_map(|diagnostic| attribute_one(diagnostic, segments))
+        .collect()
+}
+
+fn attribute_one(diagnostic: Diagnostic, segments: &[Segment]) -> Vec<Diagnostic> {
+    let overlapping: Vec<&Segment> = segments
+        .iter()
+        .filter(|segment| overlaps(&segment.composed, &diagnostic.span))
+        .collect();
+
+    if overlapping.is_empty() {
+        return vec![diagnostic];
+    }
+
+    overlapping
+        .into_iter()
+        .map(|segment| {
+            let clipped_start = diagnostic.span.start.max(segment.composed.start);
+            let clipped_end = diagnostic.span.end.min(segment.composed.end);
+            let shift = segment.file_offset as isize - segment.composed.start as isize;
+            let shift_offset = |offset: usize| (offset as isize + shift) as usize;
+            Diagnostic::new(
+                shift_offset(clipped_start)..shift_offset(clipped_end),
+                diagnostic.severity,
+                diagnostic.message.clone(),
+            )
+            .with_file(segment.file.clone())
+        })
+        .collect()
+}
+
+fn overlaps(segment: &std::ops::Range<usize>, span: &std::ops::Range<usize>) -> bool {
+    if span.is_empty() {
+        segment.contains(&span.start)
+    } else {
+        segment.start < span.end && span.start < segment.end
+    }
+}
+
+fn inline_includes_rec(
+    path: &Path,
+    policy: IncludePolicy,
+    search_path: &SearchPath,
+    visited: &mut HashSet<PathBuf>,
+) -> Result<(String, Vec<Segment>), Vec<Diagnostic>> {
+    let canonical = fs::canonicalize(path).map_err(|error| vec![read_error(path, &error)])?;
+
+    if !visited.insert(canonical.clone()) {
+        return Err(vec![Diagnostic::new(
+            0..0,
+            Severity::Error,
+            format!("include cycle detected: {} re-includes a file already on its inclusion chain", path.display()),
+        )
+        .with_file(path.to_path_buf())]);
+    }
+
+    let result = (|| -> Result<(String, Vec<Segment>), Vec<Diagnostic>> {
+        let source = fs::read_to_string(&canonical).map_err(|error| vec![read_error(path, &error)])?;
+
+        if policy == IncludePolicy::Ignore {
+            let len = source.len();
+            let segments = vec![Segment {
+                composed: 0..len,
+                file: path.to_path_buf(),
+                file_offset: 0,
+            }];
+            return Ok((source, segments));
+        }
+
+        let mut out = String::with_capacity(source.len());
+        let mut segments = Vec::new();
+        let mut cursor = 0usize;
+
+        for include in find_includes(&source) {
+            if include.span.start > cursor {
+                let start = out.len();
+                out.push_str(&source[cursor..include.span.start]);
+                segments.push(Segment {
+                    composed: start..out.len(),
+                    file: path.to_path_buf(),
+                    file_offset: cursor,
+                });
+            }
+
+            if policy == IncludePolicy::Deny {
+                return Err(vec![Diagnostic::new(
+                    include.span.clone(),
+                    Severity::Error,
+                    format!("refusing to resolve include \"{}\" under IncludePolicy::Deny", include.target),
+                )
+                .with_file(path.to_path_buf())]);
+            }
+
+            let resolved = search_path.find(include.target).ok_or_else(|| {
+                vec![Diagnostic::new(
+                    include.span.clone(),
+                    Severity::Error,
+                    format!("could not find include \"{}\" on the configured search path", include.target),
+                )
+                .with_file(path.to_path_buf())]
+            })?;
+            let (inlined, child_segments) =
+                inline_includes_rec(&resolved, policy, search_path, visited)?;
+            let shift = out.len();
+            segments.extend(child_segments.into_iter().map(|segment| Segment {
+                composed: segment.composed.start + shift..segment.composed.end + shift,
+                ..segment
+            }));
+            out.push_str(&inlined);
+
+            cursor = include.span.end;
+        }
+
+        if cursor < source.len() {
+            let start = out.len();
+            out.push_str(&source[cursor..]);
+            segments.push(Segment {
+                composed: start..out.len(),
+                file: path.to_path_buf(),
+                file_offset: cursor,
+            });
+        }
+
+        Ok((out, segments))
+    })();
+
+    visited.remove(&canonical);
+    result
+}
+
+fn read_error(path: &Path, error: &std::io::Error) -> Diagnostic {
+    Diagnostic::new(
+        0..0,
+        Severity::Error,
+        format!("failed to read \"{}\": {error}", path.display()),
+    )
+    .with_file(path.to_path_buf())
+}
+
+/// A single `include "...";` statement found in a source string.
+struct IncludeStmt<'a> {
+    /// Span of the whole statement, from the `include` keyword through the
+    /// trailing `;`.
+    span: std::ops::Range<usize>,
+    target: &'a str,
+}
+
+/// Find every `include "...";` statement in `source` by driving off its
+/// token stream, so a commented-out `include` (inside a `//` or `/* */`
+/// comment, which [`tokenize_qasm3`] already knows not to treat as live
+/// source) is never mistaken for a real one.
+fn find_includes(source: &str) -> Vec<IncludeStmt<'_>> {
+    let lexed = tokenize_qasm3(source);
+
+    let mut significant = Vec::new();
+    let mut offset = 0usize;
+    for token in &lexed.tokens {
+        let span = offset..offset + token.len as usize;
+        if !token.kind.is_trivia() {
+            significant.push(span.clone());
+        }
+        offset = span.end;
+    }
+
+    let mut includes = Vec::new();
+    let mut i = 0;
+    while i < significant.len() {
+        let keyword_span = &significant[i];
+        if &source[keyword_span.clone()] == "include" {
+            if let (Some(string_span), Some(semi_span)) = (significant.get(i + 1), significant.get(i + 2)) {
+                let string_text = &source[string_span.clone()];
+                let is_quoted = string_text.len() >= 2
+                    && string_text.starts_with('"')
+                    && string_text.ends_with('"');
+                if is_quoted && &source[semi_span.clone()] == ";" {
+                    includes.push(IncludeStmt {
+                        span: keyword_span.start..semi_span.end,
+                        target: &string_text[1..string_text.len() - 1],
+                    });
+                    i += 3;
+                    continue;
+                }
+            }
+        }
+        i += 1;
+    }
+    includes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_a_live_include() {
+        let source = "OPENQASM 3.0;\ninclude \"stdgates.inc\";\nqubit q;\n";
+        let includes = find_includes(source);
+        assert_eq!(includes.len(), 1);
+        assert_eq!(includes[0].target, "stdgates.inc");
+        assert_eq!(&source[includes[0].span.clone()], "include \"stdgates.inc\";");
+    }
+
+    #[test]
+    fn ignores_an_include_inside_a_block_comment() {
+        let source = "OPENQASM 3.0;\n/*\ninclude \"old.inc\";\n*/\ninclude \"new.inc\";\n";
+        let includes = find_includes(source);
+        assert_eq!(includes.len(), 1);
+        assert_eq!(includes[0].target, "new.inc");
+    }
+
+    #[test]
+    fn ignores_an_include_inside_a_line_comment() {
+        let source = "OPENQASM 3.0;\n// include \"old.inc\";\nqubit q;\n";
+        assert!(find_includes(source).is_empty());
+    }
+
+    #[test]
+    fn rejects_absolute_and_parent_dir_targets() {
+        let dir = std::env::temp_dir().join("rust_parser_test_search_path_traversal");
+        std::fs::create_dir_all(&dir).unwrap();
+        let search_path = SearchPath::new([dir.clone()]);
+
+        assert!(search_path.find("/etc/passwd").is_none());
+        assert!(search_path.find("../../../../etc/passwd").is_none());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn attribution_clips_a_diagnostic_crossing_a_segment_boundary() {
+        // Composed text: bytes 0..10 came from included.qasm, 10..20 from
+        // main.qasm. A diagnostic spanning 5..15 straddles the boundary.
+        let segments = vec![
+            Segment {
+                composed: 0..10,
+                file: PathBuf::from("included.qasm"),
+                file_offset: 100,
+            },
+            Segment {
+                composed: 10..20,
+                file: PathBuf::from("main.qasm"),
+                file_offset: 0,
+            },
+        ];
+        let diagnostic = Diagnostic::new(5..15, Severity::Error, "unclosed brace");
+
+        let attributed = attribute_diagnostics(vec![diagnostic], &segments);
+
+        assert_eq!(attributed.len(), 2);
+
+        assert_eq!(attributed[0].file.as_deref(), Some(Path::new("included.qasm")));
+        assert_eq!(attributed[0].span, 105..110);
+
+        assert_eq!(attributed[1].file.as_deref(), Some(Path::new("main.qasm")));
+        assert_eq!(attributed[1].span, 0..5);
+    }
+}
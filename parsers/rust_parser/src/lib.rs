@@ -1,5 +1,14 @@
+mod diagnostics;
+mod includes;
+mod semantics;
+mod tokens;
+
+pub use diagnostics::{render_diagnostics, Diagnostic, Severity};
+pub use includes::{IncludePolicy, SearchPath};
+pub use tokens::{tokenize_qasm3, Token, TokenKind, TokenizeResult};
+
 use oq3_source_file::{parse_source_string, SourceTrait};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 pub fn parse_qasm3(source: &str) -> Result<(), Box<dyn std::error::Error>> {
     let parsed = parse_source_string::<&str, PathBuf>(source, None, None);
@@ -11,6 +20,90 @@ pub fn parse_qasm3(source: &str) -> Result<(), Box<dyn std::error::Error>> {
     }
 }
 
+/// Parse `source` and return the individual parse errors as span-carrying
+/// [`Diagnostic`]s rather than collapsing everything into a single message.
+///
+/// Unlike [`parse_qasm3`], this preserves enough information for a caller to
+/// point at exactly where the source is malformed (see
+/// [`render_diagnostics`] for turning the result into caret-underlined
+/// reports).
+///
+/// Lexing and parsing are layered: if [`tokenize_qasm3`] reports any lexer
+/// error, parsing is skipped entirely and only the lexer errors are
+/// returned. This avoids spurious cascade errors from the parser trying to
+/// make sense of a token stream that was already known to be broken.
+pub fn parse_qasm3_diagnostics(source: &str) -> Result<(), Vec<Diagnostic>> {
+    let lexed = tokenize_qasm3(source);
+    if lexed.has_errors() {
+        return Err(lexed.errors);
+    }
+
+    let parsed = parse_source_string::<&str, PathBuf>(source, None, None);
+    if !parsed.any_parse_errors() {
+        return Ok(());
+    }
+
+    let diagnostics = parsed
+        .errors()
+        .iter()
+        .map(|error| {
+            let span = error.span();
+            Diagnostic::new(
+                usize::from(span.start())..usize::from(span.end()),
+                Severity::Error,
+                error.message(),
+            )
+        })
+        .collect();
+    Err(diagnostics)
+}
+
+/// Parse the QASM3 file at `path`, resolving its `include` statements
+/// against `QASM_PATH` plus the file's own directory.
+///
+/// This is a convenience over [`parse_qasm3_file_with_policy`] using
+/// [`IncludePolicy::Resolve`]; use that function directly to sandbox
+/// untrusted input with [`IncludePolicy::Deny`].
+pub fn parse_qasm3_file(path: impl AsRef<Path>) -> Result<(), Vec<Diagnostic>> {
+    parse_qasm3_file_with_policy(path, IncludePolicy::Resolve, SearchPath::from_env())
+}
+
+/// Parse the QASM3 file at `path`, honoring `policy` for its `include`
+/// statements and searching `search_path` (plus the file's own directory)
+/// for include targets.
+pub fn parse_qasm3_file_with_policy(
+    path: impl AsRef<Path>,
+    policy: IncludePolicy,
+    mut search_path: SearchPath,
+) -> Result<(), Vec<Diagnostic>> {
+    let path = path.as_ref();
+    if let Some(parent) = path.parent() {
+        search_path.prepend(parent.to_path_buf());
+    }
+
+    let (source, segments) = includes::inline_includes(path, policy, &search_path)?;
+    parse_qasm3_diagnostics(&source)
+        .map_err(|diagnostics| includes::attribute_diagnostics(diagnostics, &segments))
+}
+
+/// Run semantic analysis on `source`: undeclared identifiers, gate arity
+/// mismatches, type errors, and the like.
+///
+/// Semantic analysis only runs once [`tokenize_qasm3`] and the parser both
+/// report a clean source; a source that fails to lex or parse returns those
+/// errors unchanged rather than attempting to analyze a broken tree.
+pub fn analyze_qasm3(source: &str) -> Result<(), Vec<Diagnostic>> {
+    semantics::analyze(source)
+}
+
+/// Pretty-print the parsed syntax tree for `source`, in the debug-dump
+/// style rust-analyzer uses for its own tree (useful for interactively
+/// inspecting how a QASM program parses).
+pub fn dump_syntax_tree(source: &str) -> String {
+    let parsed = parse_source_string::<&str, PathBuf>(source, None, None);
+    format!("{:#?}", parsed.tree())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -20,4 +113,106 @@ mod tests {
         let source = "OPENQASM 3.0;\nqubit q;";
         assert!(parse_qasm3(source).is_ok());
     }
+
+    #[test]
+    fn test_parse_diagnostics_ok() {
+        let source = "OPENQASM 3.0;\nqubit q;";
+        assert!(parse_qasm3_diagnostics(source).is_ok());
+    }
+
+    #[test]
+    fn test_parse_diagnostics_reports_spans() {
+        let source = "OPENQASM 3.0;\nqubit q\n";
+        let diagnostics = parse_qasm3_diagnostics(source).expect_err("source is malformed");
+        assert!(!diagnostics.is_empty());
+        for diagnostic in &diagnostics {
+            assert!(diagnostic.span.start <= diagnostic.span.end);
+            assert!(diagnostic.span.end <= source.len());
+        }
+    }
+
+    #[test]
+    fn test_parse_file_resolves_include() {
+        let dir = std::env::temp_dir().join("rust_parser_test_parse_file_resolves_include");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("gates.inc"), "gate h q { }\n").unwrap();
+        let main_path = dir.join("main.qasm");
+        std::fs::write(&main_path, "OPENQASM 3.0;\ninclude \"gates.inc\";\nqubit q;\n").unwrap();
+
+        assert!(parse_qasm3_file(&main_path).is_ok());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_parse_file_attributes_errors_to_the_include() {
+        let dir = std::env::temp_dir().join("rust_parser_test_parse_file_attributes_errors");
+        std::fs::create_dir_all(&dir).unwrap();
+        let broken_include = dir.join("broken.inc");
+        std::fs::write(&broken_include, "gate h q\n").unwrap(); // missing `{ }`
+        let main_path = dir.join("main.qasm");
+        std::fs::write(&main_path, "OPENQASM 3.0;\ninclude \"broken.inc\";\nqubit q;\n").unwrap();
+
+        let diagnostics = parse_qasm3_file(&main_path).expect_err("included file is malformed");
+        assert!(diagnostics
+            .iter()
+            .any(|diagnostic| diagnostic.file.as_deref() == Some(broken_include.as_path())
+                && diagnostic.span.end <= std::fs::read_to_string(&broken_include).unwrap().len()));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_parse_file_deny_policy_rejects_include() {
+        let dir = std::env::temp_dir().join("rust_parser_test_parse_file_deny_policy");
+        std::fs::create_dir_all(&dir).unwrap();
+        let main_path = dir.join("main.qasm");
+        std::fs::write(&main_path, "OPENQASM 3.0;\ninclude \"gates.inc\";\nqubit q;\n").unwrap();
+
+        let result = parse_qasm3_file_with_policy(&main_path, IncludePolicy::Deny, SearchPath::default());
+        assert!(result.is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_tokenize_clean_source_has_no_errors() {
+        let source = "OPENQASM 3.0;\nqubit q;";
+        assert!(!tokenize_qasm3(source).has_errors());
+    }
+
+    #[test]
+    fn test_lexer_error_skips_parser() {
+        // A misspelled pragma lexes as an invalid identifier; the parser
+        // should never even run, so its (misleading) cascade errors must
+        // not show up alongside the lexer error.
+        let source = "OPENQASM 3.0;\n#pragm oops\nqubit q;";
+        let lexed = tokenize_qasm3(source);
+        assert!(lexed.has_errors());
+
+        let diagnostics = parse_qasm3_diagnostics(source).expect_err("source has a lexer error");
+        assert_eq!(diagnostics, lexed.errors);
+    }
+
+    #[test]
+    fn test_analyze_clean_source() {
+        let source = "OPENQASM 3.0;\ngate h a { }\nqubit q;\nh q;";
+        assert!(analyze_qasm3(source).is_ok());
+    }
+
+    #[test]
+    fn test_analyze_undeclared_identifier() {
+        let source = "OPENQASM 3.0;\nh q;";
+        assert!(analyze_qasm3(source).is_err());
+    }
+
+    #[test]
+    fn test_analyze_skips_on_parse_error() {
+        // A source that doesn't even parse should surface the parse error,
+        // not an analysis error about a tree that was never built.
+        let source = "OPENQASM 3.0;\nqubit q\n";
+        let parse_diagnostics = parse_qasm3_diagnostics(source).expect_err("source is malformed");
+        let analyze_diagnostics = analyze_qasm3(source).expect_err("source is malformed");
+        assert_eq!(analyze_diagnostics, parse_diagnostics);
+    }
 }
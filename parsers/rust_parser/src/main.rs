@@ -1,18 +1,83 @@
-use rust_parser::parse_qasm3;
+use rust_parser::{dump_syntax_tree, parse_qasm3_diagnostics, render_diagnostics, tokenize_qasm3};
 use std::env;
+use std::fs;
+use std::io::{self, Read};
+use std::process::ExitCode;
 
-fn main() {
-    let args: Vec<String> = env::args().collect();
-    if args.len() != 2 {
-        eprintln!("Usage: {} <qasm-string>", args[0]);
-        std::process::exit(1);
+fn main() -> ExitCode {
+    let mut args = env::args().skip(1);
+    let Some(mode) = args.next() else {
+        print_usage();
+        return ExitCode::FAILURE;
+    };
+
+    if !matches!(mode.as_str(), "parse" | "tokens" | "tree") {
+        eprintln!("Error: unknown mode \"{mode}\"");
+        print_usage();
+        return ExitCode::FAILURE;
     }
 
-    match parse_qasm3(&args[1]) {
-        Ok(_) => std::process::exit(0),
+    let source = match read_source(args.next()) {
+        Ok(source) => source,
         Err(e) => {
-            eprintln!("Error: {}", e);
-            std::process::exit(1)
+            eprintln!("Error: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match mode.as_str() {
+        "parse" => run_parse(&source),
+        "tokens" => run_tokens(&source),
+        "tree" => run_tree(&source),
+        _ => unreachable!("mode was validated above"),
+    }
+}
+
+fn print_usage() {
+    eprintln!("Usage: rust_parser <parse|tokens|tree> [path]");
+    eprintln!("       omit path, or pass \"-\", to read the source from stdin");
+}
+
+/// Read the source to operate on from `path`, or from stdin when `path` is
+/// absent or `"-"`.
+fn read_source(path: Option<String>) -> io::Result<String> {
+    match path.as_deref() {
+        None | Some("-") => {
+            let mut source = String::new();
+            io::stdin().read_to_string(&mut source)?;
+            Ok(source)
         }
+        Some(path) => fs::read_to_string(path),
     }
 }
+
+fn run_parse(source: &str) -> ExitCode {
+    match parse_qasm3_diagnostics(source) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(diagnostics) => {
+            eprint!("{}", render_diagnostics(source, &diagnostics));
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run_tokens(source: &str) -> ExitCode {
+    let lexed = tokenize_qasm3(source);
+    let mut offset = 0usize;
+    for token in &lexed.tokens {
+        let len = token.len as usize;
+        println!("{:>6}..{:<6} {:?}", offset, offset + len, token.kind);
+        offset += len;
+    }
+
+    if lexed.has_errors() {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn run_tree(source: &str) -> ExitCode {
+    println!("{}", dump_syntax_tree(source));
+    ExitCode::SUCCESS
+}
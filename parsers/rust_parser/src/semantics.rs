@@ -0,0 +1,31 @@
+use crate::diagnostics::{Diagnostic, Severity};
+use crate::parse_qasm3_diagnostics;
+use oq3_semantics::syntax_to_semantics::analyze_source_string;
+use std::path::PathBuf;
+
+/// Run semantic analysis on `source`. The source is first run through
+/// [`parse_qasm3_diagnostics`]; if it fails to lex or parse, those errors are
+/// returned unchanged and semantic analysis does not run.
+pub(crate) fn analyze(source: &str) -> Result<(), Vec<Diagnostic>> {
+    parse_qasm3_diagnostics(source)?;
+
+    let analyzed = analyze_source_string::<&str, PathBuf>(source, None, None);
+    let errors: Vec<Diagnostic> = analyzed
+        .errors()
+        .iter()
+        .map(|error| {
+            let span = error.span();
+            Diagnostic::new(
+                usize::from(span.start())..usize::from(span.end()),
+                Severity::Error,
+                error.message(),
+            )
+        })
+        .collect();
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
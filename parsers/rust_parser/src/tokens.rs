@@ -0,0 +1,47 @@
+use crate::diagnostics::{Diagnostic, Severity};
+
+pub use oq3_lexer::{Token, TokenKind};
+
+/// The token stream for a source string, plus any lexical errors found
+/// along the way (e.g. an unterminated string, or an identifier containing
+/// an invalid character).
+#[derive(Debug, Default)]
+pub struct TokenizeResult {
+    pub tokens: Vec<Token>,
+    pub errors: Vec<Diagnostic>,
+}
+
+impl TokenizeResult {
+    pub fn has_errors(&self) -> bool {
+        !self.errors.is_empty()
+    }
+}
+
+/// Lex `source` into its token stream, reporting lexical errors (malformed
+/// tokens such as an invalid identifier or an unterminated string) as
+/// span-carrying [`Diagnostic`]s rather than folding them into the parser's
+/// output.
+///
+/// [`parse_qasm3_diagnostics`](crate::parse_qasm3_diagnostics) runs this
+/// first and skips parsing entirely when it reports any error.
+pub fn tokenize_qasm3(source: &str) -> TokenizeResult {
+    let mut tokens = Vec::new();
+    let mut errors = Vec::new();
+    let mut offset = 0usize;
+
+    for token in oq3_lexer::tokenize(source) {
+        let len = token.len as usize;
+        let span = offset..offset + len;
+        if token.kind.is_error() {
+            errors.push(Diagnostic::new(
+                span.clone(),
+                Severity::Error,
+                format!("invalid token: {:?}", token.kind),
+            ));
+        }
+        offset = span.end;
+        tokens.push(token);
+    }
+
+    TokenizeResult { tokens, errors }
+}
@@ -0,0 +1,92 @@
+use std::fs;
+use std::path::Path;
+
+use oq3_source_file::SourceTrait;
+use walkdir::WalkDir;
+
+/// Corpus-based regression suite, in the spirit of rust-analyzer's
+/// `dir_tests`: every `.qasm` fixture under `tests/data/{ok,err}` is parsed
+/// and checked against a sibling `.rast` snapshot of its syntax tree and
+/// error list. A fixture with no committed snapshot fails the suite rather
+/// than silently adopting whatever the parser currently emits as "expected":
+/// run with `UPDATE_EXPECT=1` to (re)generate snapshots after adding a
+/// fixture or making an intentional parser change, and commit the result.
+#[test]
+fn corpus() {
+    run_dir("tests/data/ok", false);
+    run_dir("tests/data/err", true);
+}
+
+fn run_dir(dir: &str, expect_errors: bool) {
+    let update = std::env::var_os("UPDATE_EXPECT").is_some();
+    let mut checked = 0;
+
+    for entry in WalkDir::new(dir).into_iter().filter_map(Result::ok) {
+        let path = entry.path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some("qasm") {
+            continue;
+        }
+        checked += 1;
+
+        let source = fs::read_to_string(path)
+            .unwrap_or_else(|e| panic!("failed to read {}: {e}", path.display()));
+
+        let diagnostics = rust_parser::parse_qasm3_diagnostics(&source);
+        if expect_errors {
+            assert!(
+                diagnostics.is_err(),
+                "{} is under tests/data/err but parsed with no errors",
+                path.display()
+            );
+        } else {
+            assert!(
+                diagnostics.is_ok(),
+                "{} is under tests/data/ok but failed to parse: {:?}",
+                path.display(),
+                diagnostics.unwrap_err()
+            );
+        }
+
+        check_snapshot(path, &snapshot_for(&source), update);
+    }
+
+    assert!(checked > 0, "no .qasm fixtures found under {dir}");
+}
+
+fn snapshot_for(source: &str) -> String {
+    let parsed = oq3_source_file::parse_source_string::<&str, std::path::PathBuf>(source, None, None);
+    let mut out = format!("{:#?}\n", parsed.tree());
+    if parsed.any_parse_errors() {
+        out.push_str("\nerrors:\n");
+        for error in parsed.errors() {
+            out.push_str(&format!("  {error:?}\n"));
+        }
+    }
+    out
+}
+
+fn check_snapshot(qasm_path: &Path, actual: &str, update: bool) {
+    let snapshot_path = qasm_path.with_extension("rast");
+
+    if update {
+        fs::write(&snapshot_path, actual)
+            .unwrap_or_else(|e| panic!("failed to write {}: {e}", snapshot_path.display()));
+        return;
+    }
+
+    assert!(
+        snapshot_path.exists(),
+        "{} has no snapshot; run with UPDATE_EXPECT=1 to generate {} and commit it",
+        qasm_path.display(),
+        snapshot_path.display()
+    );
+
+    let expected = fs::read_to_string(&snapshot_path)
+        .unwrap_or_else(|e| panic!("failed to read {}: {e}", snapshot_path.display()));
+    assert_eq!(
+        expected, actual,
+        "{} does not match {}; rerun with UPDATE_EXPECT=1 to regenerate",
+        qasm_path.display(),
+        snapshot_path.display()
+    );
+}